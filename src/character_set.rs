@@ -0,0 +1,918 @@
+use std::{collections::BTreeSet, ops::RangeInclusive};
+use unicode_script::UnicodeScript;
+
+const JIS_X0208_LEVEL_1_KANJI: &str = include_str!("data/jis_x0208_level_1.txt");
+
+pub struct CharacterSet {}
+
+impl CharacterSet {
+    /// Printable ASCII (`U+0020..=U+007E`), same as
+    /// [`CharacterSet::ascii_printable`]. Control characters are no longer
+    /// included.
+    pub fn english() -> Vec<char> {
+        Self::ascii_printable()
+    }
+
+    /// Printable ASCII characters (`U+0020..=U+007E`). Control characters are
+    /// left out since they have no visible glyph.
+    pub fn ascii_printable() -> Vec<char> {
+        Self::from_range(0x0020..=0x007e)
+    }
+
+    /// Printable characters of the Latin-1 Supplement block
+    /// (`U+00A0..=U+00FF`).
+    pub fn latin1() -> Vec<char> {
+        Self::from_range(0x00a0..=0x00ff)
+            .into_iter()
+            .filter(|c| !is_format(*c))
+            .collect()
+    }
+
+    /// Assigned characters of the Latin Extended-A and Latin Extended-B
+    /// blocks (`U+0100..=U+024F`).
+    pub fn latin_extended() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[
+            UnicodeBlock::LatinExtendedA,
+            UnicodeBlock::LatinExtendedB,
+        ]))
+    }
+
+    /// Printable ASCII, Latin-1 and Latin Extended-A and -B.
+    pub fn western_european() -> Vec<char> {
+        Self::merge(&[&Self::english(), &Self::latin1(), &Self::latin_extended()])
+    }
+
+    /// Assigned characters of the Greek and Coptic block
+    /// (`U+0370..=U+03FF`).
+    pub fn greek() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::GreekAndCoptic]))
+    }
+
+    /// Assigned characters of the Cyrillic block (`U+0400..=U+04FF`).
+    pub fn cyrillic() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::Cyrillic]))
+    }
+
+    /// Assigned characters of the Arabic block (`U+0600..=U+06FF`).
+    pub fn arabic() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::Arabic]))
+    }
+
+    /// Assigned characters of the Hiragana block (`U+3040..=U+309F`).
+    pub fn hiragana() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::Hiragana]))
+    }
+
+    /// Assigned characters of the Katakana block (`U+30A0..=U+30FF`).
+    pub fn katakana() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::Katakana]))
+    }
+
+    /// Assigned Hiragana, Katakana and halfwidth Katakana characters (252
+    /// characters).
+    pub fn japanese_kana() -> Vec<char> {
+        Self::assigned(Self::from_ranges(&[
+            UnicodeBlock::Hiragana.range(),
+            UnicodeBlock::Katakana.range(),
+            0xff61..=0xff9f,
+        ]))
+    }
+
+    /// The 2,965 level 1 kanji of JIS X 0208, which cover the large majority
+    /// of everyday Japanese text.
+    pub fn kanji_common() -> Vec<char> {
+        Self::from_string(JIS_X0208_LEVEL_1_KANJI)
+    }
+
+    /// Hangul Syllables (`U+AC00..=U+D7A3`, 11,172 characters).
+    ///
+    /// As with [`CharacterSet::cjk_unified`], this needs an atlas well above
+    /// 1024×1024.
+    pub fn hangul_syllables() -> Vec<char> {
+        Self::from_range(0xac00..=0xd7a3)
+    }
+
+    /// CJK Unified Ideographs block (`U+4E00..=U+9FFF`, 20,992 code points).
+    ///
+    /// A set this large needs an atlas well above 1024×1024. If the text to
+    /// be displayed is known in advance, [`CharacterSet::from_string`]
+    /// produces a much smaller set.
+    pub fn cjk_unified() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[UnicodeBlock::CjkUnifiedIdeographs]))
+    }
+
+    /// CJK Unified Ideographs Extension A block (`U+3400..=U+4DBF`, 6,592
+    /// code points).
+    ///
+    /// As with [`CharacterSet::cjk_unified`], this needs an atlas well above
+    /// 1024×1024.
+    pub fn cjk_extension_a() -> Vec<char> {
+        Self::assigned(Self::from_blocks(&[
+            UnicodeBlock::CjkUnifiedIdeographsExtensionA,
+        ]))
+    }
+
+    /// Assigned characters of the blocks from Miscellaneous Symbols and
+    /// Pictographs through Supplemental Symbols and Pictographs
+    /// (`U+1F300..=U+1F9FF`).
+    ///
+    /// The range also covers non-emoji symbol blocks: Alchemical Symbols,
+    /// Geometric Shapes Extended and Supplemental Arrows-C.
+    pub fn emoji() -> Vec<char> {
+        Self::assigned(Self::from_range(0x1f300..=0x1f9ff))
+    }
+
+    /// Every assigned character whose Unicode script property is `script`, or
+    /// whose script extensions include it (e.g. the Greek perispomeni and
+    /// ypogegrammeni combining marks). Control and invisible formatting
+    /// characters are skipped.
+    ///
+    /// Unlike block-based sets, this also picks up characters of the script
+    /// that live outside its main block (e.g. polytonic Greek in Greek
+    /// Extended).
+    pub fn for_script(script: Script) -> Vec<char> {
+        (0..=0x10ffff)
+            .filter_map(std::char::from_u32)
+            .filter(|c| {
+                has_script(*c, script.to_unicode_script()) && !c.is_control() && !is_format(*c)
+            })
+            .collect()
+    }
+
+    /// Like [`CharacterSet::for_script`], with the printable Common-script
+    /// characters (space, digits, punctuation and basic symbols) of the Basic
+    /// Latin, Latin-1 Supplement and General Punctuation blocks added.
+    pub fn for_script_with_common(script: Script) -> Vec<char> {
+        let common: Vec<_> = Self::from_blocks(&[
+            UnicodeBlock::BasicLatin,
+            UnicodeBlock::Latin1Supplement,
+            UnicodeBlock::GeneralPunctuation,
+        ])
+        .into_iter()
+        .filter(|c| {
+            c.script() == unicode_script::Script::Common && !c.is_control() && !is_format(*c)
+        })
+        .collect();
+        Self::merge(&[&Self::for_script(script), &common])
+    }
+
+    /// Collects the unique characters of `s`, sorted. No filtering is done,
+    /// so control and format characters such as `'\n'` are kept; use
+    /// [`CharacterSet::from_text`] to build a set for a glyph atlas.
+    pub fn from_string(s: &str) -> Vec<char> {
+        s.chars().collect::<BTreeSet<_>>().into_iter().collect()
+    }
+
+    /// Collects the unique characters of all `texts`, sorted. Control and
+    /// invisible formatting characters (zero-width joiners, directional marks,
+    /// variation selectors, line separators...) are skipped, since they are
+    /// not rendered as glyphs of their own.
+    pub fn from_text<I, S>(texts: I) -> Vec<char>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut chars = BTreeSet::new();
+        for text in texts {
+            chars.extend(
+                text.as_ref()
+                    .chars()
+                    .filter(|c| !c.is_control() && !is_format(*c)),
+            );
+        }
+        chars.into_iter().collect()
+    }
+
+    /// Merges several character sets into one, sorted and without duplicates.
+    pub fn merge(sets: &[&[char]]) -> Vec<char> {
+        sets.iter()
+            .flat_map(|set| set.iter().copied())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Characters in `a` or `b`, sorted and without duplicates.
+    pub fn union(a: &[char], b: &[char]) -> Vec<char> {
+        Self::merge(&[a, b])
+    }
+
+    /// Characters in `a` that are not in `b`, sorted and without duplicates.
+    pub fn difference(a: &[char], b: &[char]) -> Vec<char> {
+        CharacterSetBuilder::new().add_chars(a).remove(b).build()
+    }
+
+    /// Collects every valid Unicode scalar value in `range`. Surrogates and
+    /// values above `U+10FFFF` are skipped rather than causing a panic.
+    pub fn from_range(range: RangeInclusive<u32>) -> Vec<char> {
+        range.filter_map(std::char::from_u32).collect()
+    }
+
+    /// Collects every valid Unicode scalar value in any of `ranges`, sorted and
+    /// without duplicates.
+    pub fn from_ranges(ranges: &[RangeInclusive<u32>]) -> Vec<char> {
+        ranges
+            .iter()
+            .cloned()
+            .flatten()
+            .filter_map(std::char::from_u32)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Collects every valid Unicode scalar value in any of `blocks`, sorted
+    /// and without duplicates.
+    pub fn from_blocks(blocks: &[UnicodeBlock]) -> Vec<char> {
+        let ranges: Vec<_> = blocks.iter().map(|block| block.range()).collect();
+        Self::from_ranges(&ranges)
+    }
+
+    /// Like [`CharacterSet::from_range`], but noncharacters (`U+FDD0..=U+FDEF`
+    /// and the last two code points of every plane) are skipped as well.
+    pub fn from_range_without_noncharacters(range: RangeInclusive<u32>) -> Vec<char> {
+        range
+            .filter_map(std::char::from_u32)
+            .filter(|c| !is_noncharacter(*c))
+            .collect()
+    }
+
+    fn assigned(chars: Vec<char>) -> Vec<char> {
+        chars.into_iter().filter(|c| is_assigned(*c)).collect()
+    }
+}
+
+/// Incrementally composes a character set. The result is always sorted and
+/// free of duplicates, independently of the order of the operations.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CharacterSetBuilder {
+    chars: BTreeSet<char>,
+}
+
+impl CharacterSetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_chars(mut self, chars: &[char]) -> Self {
+        self.chars.extend(chars.iter().copied());
+        self
+    }
+
+    /// Adds every valid Unicode scalar value in `range`.
+    pub fn add_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.chars.extend(range.filter_map(std::char::from_u32));
+        self
+    }
+
+    /// Adds the characters of `text`, with the same filtering as
+    /// [`CharacterSet::from_text`].
+    pub fn add_text(mut self, text: &str) -> Self {
+        self.chars.extend(CharacterSet::from_text([text]));
+        self
+    }
+
+    pub fn remove(mut self, chars: &[char]) -> Self {
+        for c in chars {
+            self.chars.remove(c);
+        }
+        self
+    }
+
+    pub fn remove_range(mut self, range: RangeInclusive<u32>) -> Self {
+        for c in range.filter_map(std::char::from_u32) {
+            self.chars.remove(&c);
+        }
+        self
+    }
+
+    pub fn build(self) -> Vec<char> {
+        self.chars.into_iter().collect()
+    }
+}
+
+/// Named Unicode blocks, for use with [`CharacterSet::from_blocks`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum UnicodeBlock {
+    BasicLatin,
+    Latin1Supplement,
+    LatinExtendedA,
+    LatinExtendedB,
+    GreekAndCoptic,
+    Cyrillic,
+    Arabic,
+    GeneralPunctuation,
+    CurrencySymbols,
+    LetterlikeSymbols,
+    Arrows,
+    MathematicalOperators,
+    BoxDrawing,
+    GeometricShapes,
+    Hiragana,
+    Katakana,
+    CjkUnifiedIdeographsExtensionA,
+    CjkUnifiedIdeographs,
+}
+
+impl UnicodeBlock {
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            UnicodeBlock::BasicLatin => 0x0000..=0x007f,
+            UnicodeBlock::Latin1Supplement => 0x0080..=0x00ff,
+            UnicodeBlock::LatinExtendedA => 0x0100..=0x017f,
+            UnicodeBlock::LatinExtendedB => 0x0180..=0x024f,
+            UnicodeBlock::GreekAndCoptic => 0x0370..=0x03ff,
+            UnicodeBlock::Cyrillic => 0x0400..=0x04ff,
+            UnicodeBlock::Arabic => 0x0600..=0x06ff,
+            UnicodeBlock::GeneralPunctuation => 0x2000..=0x206f,
+            UnicodeBlock::CurrencySymbols => 0x20a0..=0x20cf,
+            UnicodeBlock::LetterlikeSymbols => 0x2100..=0x214f,
+            UnicodeBlock::Arrows => 0x2190..=0x21ff,
+            UnicodeBlock::MathematicalOperators => 0x2200..=0x22ff,
+            UnicodeBlock::BoxDrawing => 0x2500..=0x257f,
+            UnicodeBlock::GeometricShapes => 0x25a0..=0x25ff,
+            UnicodeBlock::Hiragana => 0x3040..=0x309f,
+            UnicodeBlock::Katakana => 0x30a0..=0x30ff,
+            UnicodeBlock::CjkUnifiedIdeographsExtensionA => 0x3400..=0x4dbf,
+            UnicodeBlock::CjkUnifiedIdeographs => 0x4e00..=0x9fff,
+        }
+    }
+}
+
+/// Unicode scripts, for use with [`CharacterSet::for_script`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Script {
+    Common,
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Syriac,
+    Thaana,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Sinhala,
+    Thai,
+    Lao,
+    Tibetan,
+    Myanmar,
+    Georgian,
+    Hangul,
+    Ethiopic,
+    Khmer,
+    Mongolian,
+    Hiragana,
+    Katakana,
+    Bopomofo,
+    Han,
+}
+
+impl Script {
+    fn to_unicode_script(self) -> unicode_script::Script {
+        match self {
+            Script::Common => unicode_script::Script::Common,
+            Script::Latin => unicode_script::Script::Latin,
+            Script::Greek => unicode_script::Script::Greek,
+            Script::Cyrillic => unicode_script::Script::Cyrillic,
+            Script::Armenian => unicode_script::Script::Armenian,
+            Script::Hebrew => unicode_script::Script::Hebrew,
+            Script::Arabic => unicode_script::Script::Arabic,
+            Script::Syriac => unicode_script::Script::Syriac,
+            Script::Thaana => unicode_script::Script::Thaana,
+            Script::Devanagari => unicode_script::Script::Devanagari,
+            Script::Bengali => unicode_script::Script::Bengali,
+            Script::Gurmukhi => unicode_script::Script::Gurmukhi,
+            Script::Gujarati => unicode_script::Script::Gujarati,
+            Script::Tamil => unicode_script::Script::Tamil,
+            Script::Telugu => unicode_script::Script::Telugu,
+            Script::Kannada => unicode_script::Script::Kannada,
+            Script::Malayalam => unicode_script::Script::Malayalam,
+            Script::Sinhala => unicode_script::Script::Sinhala,
+            Script::Thai => unicode_script::Script::Thai,
+            Script::Lao => unicode_script::Script::Lao,
+            Script::Tibetan => unicode_script::Script::Tibetan,
+            Script::Myanmar => unicode_script::Script::Myanmar,
+            Script::Georgian => unicode_script::Script::Georgian,
+            Script::Hangul => unicode_script::Script::Hangul,
+            Script::Ethiopic => unicode_script::Script::Ethiopic,
+            Script::Khmer => unicode_script::Script::Khmer,
+            Script::Mongolian => unicode_script::Script::Mongolian,
+            Script::Hiragana => unicode_script::Script::Hiragana,
+            Script::Katakana => unicode_script::Script::Katakana,
+            Script::Bopomofo => unicode_script::Script::Bopomofo,
+            Script::Han => unicode_script::Script::Han,
+        }
+    }
+}
+
+// Characters shared between specific scripts (e.g. Greek combining marks with
+// an Inherited script) list them in their Script_Extensions. Common and
+// Inherited extensions intersect every script, so only the plain Script
+// property is checked when either side is Common or Inherited.
+fn has_script(c: char, script: unicode_script::Script) -> bool {
+    let extension = c.script_extension();
+    if extension.is_common()
+        || extension.is_inherited()
+        || script == unicode_script::Script::Common
+        || script == unicode_script::Script::Inherited
+    {
+        c.script() == script
+    } else {
+        extension.contains_script(script)
+    }
+}
+
+fn is_assigned(c: char) -> bool {
+    c.script() != unicode_script::Script::Unknown
+}
+
+fn is_noncharacter(c: char) -> bool {
+    let x = c as u32;
+    (0xfdd0..=0xfdef).contains(&x) || x & 0xfffe == 0xfffe
+}
+
+// Invisible format characters (general category Cf, plus variation selectors
+// and the like) and the line and paragraph separators (Zl, Zp). The Cf
+// prepended concatenation marks (U+0600..=U+0605, U+06DD, U+070F,
+// U+0890..=U+0891, U+08E2, U+110BD, U+110CD) are left out on purpose: they
+// are rendered as visible glyphs.
+fn is_format(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00ad
+            | 0x034f
+            | 0x061c
+            | 0x180b..=0x180f
+            | 0x200b..=0x200f
+            | 0x2028..=0x202e
+            | 0x2060..=0x206f
+            | 0xfe00..=0xfe0f
+            | 0xfeff
+            | 0xfff9..=0xfffb
+            | 0x13430..=0x1343f
+            | 0x1bca0..=0x1bca3
+            | 0x1d173..=0x1d17a
+            | 0xe0000..=0xe0fff
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english() {
+        assert_eq!(CharacterSet::english(), CharacterSet::ascii_printable());
+    }
+
+    #[test]
+    fn ascii_printable() {
+        let chars = CharacterSet::ascii_printable();
+        assert_eq!(chars.len(), 95);
+        assert_eq!(chars.first(), Some(&' '));
+        assert_eq!(chars.last(), Some(&'~'));
+        assert!(chars.iter().all(|c| !c.is_control()));
+    }
+
+    #[test]
+    fn latin1() {
+        let chars = CharacterSet::latin1();
+        assert_eq!(chars.len(), 0x5f);
+        assert!(!chars.contains(&'\u{ad}'));
+        assert!(chars.contains(&'é'));
+        assert!(chars.contains(&'ß'));
+        assert!(chars.iter().all(|c| !c.is_control()));
+    }
+
+    #[test]
+    fn latin_extended() {
+        let chars = CharacterSet::latin_extended();
+        assert_eq!(chars.len(), 0x150);
+        assert!(chars.contains(&'ł'));
+        assert!(!chars.contains(&'é'));
+        assert_eq!(chars.first(), Some(&'\u{100}'));
+        assert_eq!(chars.last(), Some(&'\u{24f}'));
+    }
+
+    #[test]
+    fn western_european() {
+        let chars = CharacterSet::western_european();
+        assert_eq!(chars.len(), 95 + 0x5f + 0x150);
+        assert!(chars.contains(&'a'));
+        assert!(chars.contains(&'é'));
+        assert!(chars.contains(&'ß'));
+        assert!(chars.contains(&'ł'));
+        assert!(!chars.contains(&'\u{ad}'));
+        assert!(chars.iter().all(|c| !c.is_control()));
+    }
+
+    #[test]
+    fn greek() {
+        let chars = CharacterSet::greek();
+        assert_eq!(chars.first(), Some(&'\u{370}'));
+        assert_eq!(chars.last(), Some(&'\u{3ff}'));
+        assert!(chars.contains(&'λ'));
+        assert!(!chars.contains(&'\u{378}'));
+        assert!(!chars.contains(&'\u{3a2}'));
+    }
+
+    #[test]
+    fn cyrillic() {
+        let chars = CharacterSet::cyrillic();
+        assert_eq!(chars.first(), Some(&'\u{400}'));
+        assert_eq!(chars.last(), Some(&'\u{4ff}'));
+        assert!(chars.contains(&'Ж'));
+    }
+
+    #[test]
+    fn arabic() {
+        let chars = CharacterSet::arabic();
+        assert_eq!(chars.first(), Some(&'\u{600}'));
+        assert_eq!(chars.last(), Some(&'\u{6ff}'));
+        assert!(chars.contains(&'ب'));
+    }
+
+    #[test]
+    fn hiragana() {
+        let chars = CharacterSet::hiragana();
+        assert_eq!(chars.first(), Some(&'\u{3041}'));
+        assert_eq!(chars.last(), Some(&'\u{309f}'));
+        assert!(!chars.contains(&'\u{3040}'));
+        assert!(!chars.contains(&'\u{3097}'));
+        assert!(!chars.contains(&'\u{3098}'));
+        assert!(chars.contains(&'あ'));
+        assert!(!chars.contains(&'ア'));
+    }
+
+    #[test]
+    fn katakana() {
+        let chars = CharacterSet::katakana();
+        assert_eq!(chars.len(), 0x60);
+        assert!(chars.contains(&'ア'));
+        assert!(!chars.contains(&'あ'));
+    }
+
+    #[test]
+    fn japanese_kana() {
+        let chars = CharacterSet::japanese_kana();
+        assert_eq!(chars.len(), 252);
+        assert!(!chars.contains(&'\u{3040}'));
+        assert!(chars.contains(&'あ'));
+        assert!(chars.contains(&'ア'));
+        assert!(chars.contains(&'ｱ'));
+        assert!(!chars.contains(&'Ａ'));
+    }
+
+    #[test]
+    fn kanji_common() {
+        let chars = CharacterSet::kanji_common();
+        assert_eq!(chars.len(), 2965);
+        assert!(chars.contains(&'亜'));
+        assert!(chars.contains(&'日'));
+        assert!(chars.contains(&'本'));
+        assert!(chars.contains(&'腕'));
+        assert!(chars.iter().all(|c| ('\u{4e00}'..='\u{9fff}').contains(c)));
+    }
+
+    #[test]
+    fn hangul_syllables() {
+        let chars = CharacterSet::hangul_syllables();
+        assert_eq!(chars.len(), 11172);
+        assert_eq!(chars.first(), Some(&'가'));
+        assert_eq!(chars.last(), Some(&'힣'));
+    }
+
+    #[test]
+    fn cjk_presets_compose() {
+        let kana = CharacterSet::japanese_kana();
+        let kanji = CharacterSet::kanji_common();
+        let chars = CharacterSetBuilder::new()
+            .add_chars(&kana)
+            .add_chars(&kanji)
+            .add_chars(&kana)
+            .build();
+        assert_eq!(chars.len(), kana.len() + kanji.len());
+    }
+
+    #[test]
+    fn cjk_unified() {
+        let chars = CharacterSet::cjk_unified();
+        assert_eq!(chars.len(), 20992);
+        assert_eq!(chars.first(), Some(&'\u{4e00}'));
+        assert_eq!(chars.last(), Some(&'\u{9fff}'));
+        assert!(chars.contains(&'世'));
+    }
+
+    #[test]
+    fn cjk_extension_a() {
+        let chars = CharacterSet::cjk_extension_a();
+        assert_eq!(chars.len(), 6592);
+        assert_eq!(chars.first(), Some(&'\u{3400}'));
+        assert_eq!(chars.last(), Some(&'\u{4dbf}'));
+    }
+
+    #[test]
+    fn emoji() {
+        let chars = CharacterSet::emoji();
+        assert_eq!(chars.first(), Some(&'\u{1f300}'));
+        assert_eq!(chars.last(), Some(&'\u{1f9ff}'));
+        assert!(chars.contains(&'🍕'));
+        assert!(chars.contains(&'👍'));
+        assert!(chars.contains(&'\u{1f700}'));
+        assert!(chars.contains(&'😀'));
+        assert!(chars.contains(&'🦀'));
+    }
+
+    #[test]
+    fn for_script() {
+        let chars = CharacterSet::for_script(Script::Greek);
+        assert!(chars.contains(&'λ'));
+        assert!(chars.contains(&'Ω'));
+        assert!(chars.contains(&'ἀ'));
+        assert!(chars.contains(&'ῷ'));
+        assert!(!chars.contains(&'a'));
+        assert!(!chars.contains(&'1'));
+        assert!(!chars.contains(&'\u{378}'));
+    }
+
+    #[test]
+    fn for_script_includes_script_extensions() {
+        let chars = CharacterSet::for_script(Script::Greek);
+        assert!(chars.contains(&'\u{342}'));
+        assert!(chars.contains(&'\u{345}'));
+    }
+
+    #[test]
+    fn for_script_common() {
+        let chars = CharacterSet::for_script(Script::Common);
+        assert!(chars.contains(&'1'));
+        assert!(chars.contains(&'.'));
+        assert!(!chars.contains(&'a'));
+        assert!(!chars.contains(&'\0'));
+        assert!(!chars.contains(&'\n'));
+        assert!(!chars.contains(&'\u{200d}'));
+        assert!(chars.iter().all(|c| !c.is_control()));
+    }
+
+    #[test]
+    fn for_script_outside_main_block() {
+        let chars = CharacterSet::for_script(Script::Cyrillic);
+        assert!(chars.contains(&'Ж'));
+        assert!(chars.contains(&'\u{1c80}'));
+        let chars = CharacterSet::for_script(Script::Han);
+        assert!(chars.contains(&'世'));
+        assert!(chars.contains(&'\u{3400}'));
+    }
+
+    #[test]
+    fn for_script_with_common() {
+        let chars = CharacterSet::for_script_with_common(Script::Greek);
+        assert!(chars.contains(&'λ'));
+        assert!(chars.contains(&'ἀ'));
+        assert!(chars.contains(&' '));
+        assert!(chars.contains(&'1'));
+        assert!(chars.contains(&'.'));
+        assert!(chars.contains(&'—'));
+        assert!(!chars.contains(&'a'));
+        assert!(!chars.contains(&'\u{200d}'));
+        assert!(chars.iter().all(|c| !c.is_control()));
+    }
+
+    #[test]
+    fn from_string() {
+        let chars = CharacterSet::from_string("hello, world");
+        assert_eq!(chars, vec![' ', ',', 'd', 'e', 'h', 'l', 'o', 'r', 'w']);
+    }
+
+    #[test]
+    fn from_string_order_is_stable() {
+        assert_eq!(
+            CharacterSet::from_string("cab"),
+            CharacterSet::from_string("bca")
+        );
+    }
+
+    #[test]
+    fn from_string_multibyte() {
+        let chars = CharacterSet::from_string("žaža");
+        assert_eq!(chars, vec!['a', 'ž']);
+    }
+
+    #[test]
+    fn from_empty_string() {
+        assert!(CharacterSet::from_string("").is_empty());
+    }
+
+    #[test]
+    fn from_text() {
+        let chars = CharacterSet::from_text(["Héllo, wörld!"]);
+        assert_eq!(
+            chars,
+            vec![' ', '!', ',', 'H', 'd', 'l', 'o', 'r', 'w', 'é', 'ö']
+        );
+    }
+
+    #[test]
+    fn from_text_multiple_strings() {
+        let chars = CharacterSet::from_text(vec![String::from("ba"), String::from("cab")]);
+        assert_eq!(chars, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn from_text_skips_control_and_format_characters() {
+        let chars = CharacterSet::from_text([
+            "a\tb\n\u{200d}\u{feff}\u{202e}c\u{fe0f}",
+            "\u{2028}\u{2029}\u{fff9}\u{1d173}\u{180f}",
+        ]);
+        assert_eq!(chars, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn from_text_emoji() {
+        let chars = CharacterSet::from_text(["🦀👍🏽"]);
+        assert_eq!(chars, vec!['\u{1f3fd}', '\u{1f44d}', '\u{1f980}']);
+    }
+
+    #[test]
+    fn from_no_text() {
+        let texts: [&str; 0] = [];
+        assert!(CharacterSet::from_text(texts).is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let chars = CharacterSet::merge(&[&['c', 'a'], &['b', 'a'], &[]]);
+        assert_eq!(chars, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn merge_overlapping_presets() {
+        let english = CharacterSet::english();
+        let western_european = CharacterSet::western_european();
+        let chars = CharacterSet::merge(&[&english, &western_european]);
+        assert_eq!(chars, western_european);
+    }
+
+    #[test]
+    fn union() {
+        let chars = CharacterSet::union(&['c', 'a'], &['b', 'a']);
+        assert_eq!(chars, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn difference() {
+        let chars = CharacterSet::difference(&['d', 'a', 'b', 'a'], &['b', 'c']);
+        assert_eq!(chars, vec!['a', 'd']);
+    }
+
+    #[test]
+    fn builder_empty() {
+        assert!(CharacterSetBuilder::new().build().is_empty());
+    }
+
+    #[test]
+    fn builder_overlapping_adds() {
+        let chars = CharacterSetBuilder::new()
+            .add_range(0x0061..=0x0064)
+            .add_chars(&['c', 'x', 'a'])
+            .add_text("bye")
+            .build();
+        assert_eq!(chars, vec!['a', 'b', 'c', 'd', 'e', 'x', 'y']);
+    }
+
+    #[test]
+    fn builder_order_is_deterministic() {
+        let a = CharacterSetBuilder::new()
+            .add_chars(&['z', 'a'])
+            .add_range(0x0041..=0x0043)
+            .build();
+        let b = CharacterSetBuilder::new()
+            .add_range(0x0041..=0x0043)
+            .add_chars(&['a', 'z'])
+            .build();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn builder_remove() {
+        let chars = CharacterSetBuilder::new()
+            .add_text("hello")
+            .remove(&['l', 'x'])
+            .build();
+        assert_eq!(chars, vec!['e', 'h', 'o']);
+    }
+
+    #[test]
+    fn builder_remove_subrange() {
+        let chars = CharacterSetBuilder::new()
+            .add_range(0x0061..=0x0066)
+            .remove_range(0x0062..=0x0064)
+            .build();
+        assert_eq!(chars, vec!['a', 'e', 'f']);
+    }
+
+    #[test]
+    fn builder_add_range_skips_surrogates() {
+        let chars = CharacterSetBuilder::new()
+            .add_range(0xd7ff..=0xe000)
+            .build();
+        assert_eq!(chars, vec!['\u{d7ff}', '\u{e000}']);
+    }
+
+    #[test]
+    fn from_range() {
+        let chars = CharacterSet::from_range(0x0041..=0x0043);
+        assert_eq!(chars, vec!['A', 'B', 'C']);
+    }
+
+    #[test]
+    fn from_range_skips_surrogates() {
+        let chars = CharacterSet::from_range(0xd7ff..=0xe000);
+        assert_eq!(chars, vec!['\u{d7ff}', '\u{e000}']);
+    }
+
+    #[test]
+    fn from_range_skips_out_of_bounds_values() {
+        let chars = CharacterSet::from_range(0x10fffe..=0x110001);
+        assert_eq!(chars, vec!['\u{10fffe}', '\u{10ffff}']);
+    }
+
+    #[test]
+    fn from_empty_range() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let chars = CharacterSet::from_range(0x0043..=0x0041);
+        assert!(chars.is_empty());
+    }
+
+    #[test]
+    fn from_ranges() {
+        let chars = CharacterSet::from_ranges(&[0x0061..=0x0063, 0x0041..=0x0042]);
+        assert_eq!(chars, vec!['A', 'B', 'a', 'b', 'c']);
+    }
+
+    #[test]
+    fn from_overlapping_ranges() {
+        let chars = CharacterSet::from_ranges(&[0x0041..=0x0043, 0x0042..=0x0044]);
+        assert_eq!(chars, vec!['A', 'B', 'C', 'D']);
+    }
+
+    #[test]
+    fn from_ranges_skips_invalid_values() {
+        let chars = CharacterSet::from_ranges(&[0xd7ff..=0xe000, 0x10ffff..=0x110001]);
+        assert_eq!(chars, vec!['\u{d7ff}', '\u{e000}', '\u{10ffff}']);
+    }
+
+    #[test]
+    fn from_no_ranges() {
+        assert!(CharacterSet::from_ranges(&[]).is_empty());
+    }
+
+    #[test]
+    fn from_blocks() {
+        let chars =
+            CharacterSet::from_blocks(&[UnicodeBlock::Cyrillic, UnicodeBlock::GeneralPunctuation]);
+        assert_eq!(chars.len(), 0x100 + 0x70);
+        assert_eq!(chars.first(), Some(&'\u{400}'));
+        assert_eq!(chars.last(), Some(&'\u{206f}'));
+        assert!(!chars.contains(&'\u{3ff}'));
+        assert!(!chars.contains(&'\u{500}'));
+        assert!(chars.contains(&'—'));
+    }
+
+    #[test]
+    fn block_ranges() {
+        assert_eq!(UnicodeBlock::BasicLatin.range(), 0x0000..=0x007f);
+        assert_eq!(UnicodeBlock::Cyrillic.range(), 0x0400..=0x04ff);
+        assert_eq!(UnicodeBlock::CurrencySymbols.range(), 0x20a0..=0x20cf);
+        assert_eq!(UnicodeBlock::Arrows.range(), 0x2190..=0x21ff);
+    }
+
+    #[test]
+    fn from_range_without_noncharacters() {
+        let chars = CharacterSet::from_range_without_noncharacters(0xfdcf..=0xfdf0);
+        assert_eq!(chars, vec!['\u{fdcf}', '\u{fdf0}']);
+        let chars = CharacterSet::from_range_without_noncharacters(0x1fffd..=0x20000);
+        assert_eq!(chars, vec!['\u{1fffd}', '\u{20000}']);
+    }
+
+    #[test]
+    fn noncharacters() {
+        assert!(is_noncharacter('\u{fdd0}'));
+        assert!(is_noncharacter('\u{fdef}'));
+        assert!(is_noncharacter('\u{fffe}'));
+        assert!(is_noncharacter('\u{ffff}'));
+        assert!(is_noncharacter('\u{10ffff}'));
+        assert!(!is_noncharacter('\u{fdcf}'));
+        assert!(!is_noncharacter('\u{fffd}'));
+        assert!(!is_noncharacter('a'));
+    }
+}
@@ -1,3 +1,6 @@
+mod character_set;
+pub use character_set::*;
+
 #[cfg(test)]
 mod tests {
     #[test]